@@ -6,11 +6,18 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::{env, process, thread};
 use time::{now, strftime};
 use users::{get_current_uid, get_user_by_uid};
 
+mod storage;
+use storage::{QuerySample, SqliteStorage, Storage};
+
 const QUERY_SHOW_PROCESS: &'static str = "SHOW FULL PROCESSLIST";
 
 static NORMALIZE_PATTERNS: Lazy<Vec<NormalizePattern<'static>>> = Lazy::new(|| {
@@ -26,22 +33,103 @@ static NORMALIZE_PATTERNS: Lazy<Vec<NormalizePattern<'static>>> = Lazy::new(|| {
     ]
 });
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputFormat {
+    Text,
+    Json,
+    Ndjson,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            _ => Err(format!("unknown format: {} (expect text, json or ndjson)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GroupBy {
+    Query,
+    User,
+    Host,
+    Db,
+    Server,
+}
+
+impl std::str::FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "query" => Ok(GroupBy::Query),
+            "user" => Ok(GroupBy::User),
+            "host" => Ok(GroupBy::Host),
+            "db" => Ok(GroupBy::Db),
+            "server" => Ok(GroupBy::Server),
+            _ => Err(format!("unknown group-by: {} (expect query, user, host, db or server)", s)),
+        }
+    }
+}
+
+fn group_key(row: &FullProcessList, group_by: GroupBy) -> String {
+    match group_by {
+        GroupBy::Query => row.info.clone(),
+        GroupBy::User => format!("{}|{}", row.user, row.info),
+        GroupBy::Host => format!("{}|{}", row.host, row.info),
+        GroupBy::Db => format!("{}|{}", row.db, row.info),
+        GroupBy::Server => format!("{}|{}", row.server, row.info),
+    }
+}
+
+fn escape_json(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
 trait Summarize {
     fn new(limit: u32) -> Self;
-    fn show(&mut self, n_query: u32);
-    fn update(&mut self, queries: Vec<String>);
+    fn show(&mut self, n_query: u32, format: OutputFormat, timestamp: &str) -> String;
+    fn update(&mut self, rows: &[FullProcessList], group_by: GroupBy);
 }
 
-fn show_summary(summ: &HashMap<String, i64>, n_query: u32) {
+fn render_summary(summ: &HashMap<String, i64>, n_query: u32, format: OutputFormat, timestamp: &str) -> String {
     let mut pp: Vec<_> = summ.iter().collect();
     pp.sort_by(|a, b| b.1.cmp(a.1));
-
-    let mut cnt = 0;
-    for (k, v) in pp {
-        println!("{:-4} {}", v, k);
-        cnt += 1;
-        if cnt >= n_query {
-            break;
+    pp.truncate(n_query as usize);
+
+    match format {
+        OutputFormat::Text => pp.iter().map(|(k, v)| format!("{:-4} {}", v, k)).collect::<Vec<_>>().join("\n"),
+        OutputFormat::Json | OutputFormat::Ndjson => {
+            let (sep, indent) = if format == OutputFormat::Json { (",\n", "  ") } else { (",", "") };
+            let queries: Vec<String> = pp
+                .iter()
+                .map(|(k, v)| format!("{}{{\"query\":\"{}\",\"count\":{}}}", indent, escape_json(k), v))
+                .collect();
+            if format == OutputFormat::Json {
+                format!(
+                    "{{\n  \"timestamp\":\"{}\",\n  \"queries\":[\n{}\n  ]\n}}",
+                    timestamp,
+                    queries.join(sep)
+                )
+            } else {
+                format!("{{\"timestamp\":\"{}\",\"queries\":[{}]}}", timestamp, queries.join(sep))
+            }
         }
     }
 }
@@ -54,13 +142,13 @@ impl Summarize for Summarizer {
         Summarizer { counts: HashMap::new() }
     }
 
-    fn show(&mut self, n_query: u32) {
-        show_summary(&self.counts, n_query);
+    fn show(&mut self, n_query: u32, format: OutputFormat, timestamp: &str) -> String {
+        render_summary(&self.counts, n_query, format, timestamp)
     }
 
-    fn update(&mut self, queries: Vec<String>) {
-        for query in queries {
-            let count = self.counts.entry(query).or_insert(0);
+    fn update(&mut self, rows: &[FullProcessList], group_by: GroupBy) {
+        for row in rows {
+            let count = self.counts.entry(group_key(row, group_by)).or_insert(0);
             *count += 1;
         }
     }
@@ -83,7 +171,7 @@ impl Summarize for RecentSummarizer {
         }
     }
 
-    fn show(&mut self, n_query: u32) {
+    fn show(&mut self, n_query: u32, format: OutputFormat, timestamp: &str) -> String {
         let mut summ = HashMap::new();
         for qcs in &self.counts {
             for qc in qcs {
@@ -92,11 +180,11 @@ impl Summarize for RecentSummarizer {
                 *count += qc.n;
             }
         }
-        show_summary(&summ, n_query);
+        render_summary(&summ, n_query, format, timestamp)
     }
 
-    fn update(&mut self, queries: Vec<String>) {
-        let mut qs = queries;
+    fn update(&mut self, rows: &[FullProcessList], group_by: GroupBy) {
+        let mut qs: Vec<String> = rows.iter().map(|row| group_key(row, group_by)).collect();
         let mut qc = Vec::<QueryCount>::new();
         if self.counts.len() >= self.limit as usize {
             self.counts.remove(0);
@@ -116,22 +204,54 @@ impl Summarize for RecentSummarizer {
     }
 }
 
-#[allow(dead_code)]
 #[derive(Debug)]
 struct FullProcessList {
+    #[allow(dead_code)]
     id: u64,
     user: String,
     host: String,
     db: String,
+    #[allow(dead_code)]
     command: String,
     time: i32,
+    #[allow(dead_code)]
     state: String,
     info: String,
+    server: String,
 }
 
-#[derive(Debug)]
-struct ProcessList {
-    info: String,
+struct ProcessFilter {
+    filter_user: Option<String>,
+    exclude_user: Option<String>,
+    filter_host: Option<String>,
+    filter_db: Option<String>,
+    min_time: i32,
+}
+
+impl ProcessFilter {
+    fn matches(&self, row: &FullProcessList) -> bool {
+        if let Some(ref user) = self.filter_user {
+            if &row.user != user {
+                return false;
+            }
+        }
+        if let Some(ref user) = self.exclude_user {
+            if &row.user == user {
+                return false;
+            }
+        }
+        if let Some(ref host) = self.filter_host {
+            if &row.host != host {
+                return false;
+            }
+        }
+        if let Some(ref db) = self.filter_db {
+            if &row.db != db {
+                return false;
+            }
+        }
+        row.time >= self.min_time
+    }
 }
 
 struct NormalizePattern<'a> {
@@ -152,6 +272,9 @@ struct MyprofilerOption {
     interval: f32,
     delay: i32,
     top: u32,
+    format: OutputFormat,
+    group_by: GroupBy,
+    filter: ProcessFilter,
 }
 
 macro_rules! value2string {
@@ -169,13 +292,28 @@ macro_rules! value2string {
     };
 }
 
+macro_rules! value2num {
+    ($row:expr, $value:expr, $t:ty) => {
+        match $row.take($value) {
+            Some(v) => {
+                if v == Value::NULL {
+                    0 as $t
+                } else {
+                    from_value::<$t>(v)
+                }
+            }
+            None => 0 as $t,
+        }
+    };
+}
+
 macro_rules! opts2v {
     ($m:expr, $opts:expr, $opt:expr, $t:ty, $default:expr) => {
         match $m.opt_str($opt) {
             Some(v) => match v.parse::<$t>() {
                 Ok(v) => v,
                 Err(e) => {
-                    println!("e={:?}", e);
+                    eprintln!("e={:?}", e);
                     print_usage($opts);
                     process::exit(1);
                 }
@@ -193,14 +331,22 @@ pub fn normalize_query(text: &str) -> String {
     t.to_string()
 }
 
-fn get_process_list(pool: &Pool) -> Vec<ProcessList> {
+fn get_process_list(pool: &Pool, server: &str) -> Vec<FullProcessList> {
     let mut conn = pool.get_conn().unwrap();
-    let procs: Vec<ProcessList> = conn
+    let procs: Vec<FullProcessList> = conn
         .exec_iter(QUERY_SHOW_PROCESS, ())
         .map(|ret| {
             ret.map(|x| x.unwrap())
-                .map(|mut row| ProcessList {
+                .map(|mut row| FullProcessList {
+                    id: value2num!(row, "Id", u64),
+                    user: value2string!(row, "User"),
+                    host: value2string!(row, "Host"),
+                    db: value2string!(row, "db"),
+                    command: value2string!(row, "Command"),
+                    time: value2num!(row, "Time", i32),
+                    state: value2string!(row, "State"),
                     info: value2string!(row, "Info"),
+                    server: server.to_string(),
                 })
                 .filter(|x| !x.info.is_empty() && x.info != QUERY_SHOW_PROCESS.to_string())
                 .collect()
@@ -209,41 +355,218 @@ fn get_process_list(pool: &Pool) -> Vec<ProcessList> {
     procs
 }
 
+fn parse_hosts(hosts_arg: &str, default_port: i32) -> Vec<(String, i32)> {
+    hosts_arg
+        .split(',')
+        .map(|entry| match entry.split_once(':') {
+            Some((host, port)) => (
+                host.to_string(),
+                port.parse::<i32>().expect("invalid port in --host"),
+            ),
+            None => (entry.to_string(), default_port),
+        })
+        .collect()
+}
+
 fn print_usage(opts: Options) {
-    print!("{}", opts.usage("Usage: myprofiler [options]"));
+    eprint!("{}", opts.usage("Usage: myprofiler [options]\n       myprofiler report --store <path.db> [options]"));
 }
 
-fn exec_profile<T: Summarize>(pool: &Pool, mut summ: T, options: &MyprofilerOption) {
-    let mut cnt = 0;
+/// Destination for report output: stdout by default, or a file reopened on SIGHUP
+/// (so `kill -HUP` rotates the output file without restarting myprofiler).
+struct ReportSink {
+    path: Option<String>,
+    file: Option<std::fs::File>,
+    reopen: Arc<AtomicBool>,
+}
+
+impl ReportSink {
+    fn new(path: Option<String>) -> ReportSink {
+        let reopen = Arc::new(AtomicBool::new(false));
+        let file = match &path {
+            Some(p) => {
+                register_sighup_reopen(Arc::clone(&reopen));
+                Some(Self::open_file(p))
+            }
+            None => None,
+        };
+        ReportSink { path, file, reopen }
+    }
+
+    fn open_file(path: &str) -> std::fs::File {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .expect("fail open out-file")
+    }
+
+    fn write_line(&mut self, line: &str) {
+        if self.reopen.swap(false, Ordering::SeqCst) {
+            if let Some(path) = &self.path {
+                self.file = Some(Self::open_file(path));
+            }
+        }
+        match &mut self.file {
+            Some(f) => writeln!(f, "{}", line).expect("fail write report line"),
+            None => println!("{}", line),
+        }
+    }
+}
+
+fn register_sighup_reopen(reopen: Arc<AtomicBool>) {
+    let mut signals = signal_hook::iterator::Signals::new(&[signal_hook::consts::SIGHUP])
+        .expect("fail register SIGHUP handler");
+    thread::spawn(move || {
+        for _ in signals.forever() {
+            reopen.store(true, Ordering::SeqCst);
+        }
+    });
+}
+
+fn samples_from_queries(sampled_at: &str, queries: &[(String, String)]) -> Vec<QuerySample> {
+    let mut grouped: HashMap<&str, (&str, i64)> = HashMap::new();
+    for (fingerprint, text) in queries {
+        let entry = grouped.entry(fingerprint.as_str()).or_insert((text.as_str(), 0));
+        entry.1 += 1;
+    }
+    grouped
+        .into_iter()
+        .map(|(fingerprint, (text, count))| QuerySample {
+            sampled_at: sampled_at.to_string(),
+            query_fingerprint: fingerprint.to_string(),
+            query_text: text.to_string(),
+            count,
+        })
+        .collect()
+}
+
+type SharedStore = Arc<Mutex<Box<dyn Storage + Send>>>;
+
+fn sample_loop<T: Summarize + Send>(
+    pool: Pool,
+    server: String,
+    summ: &Arc<Mutex<T>>,
+    options: &Arc<MyprofilerOption>,
+    store: &Option<SharedStore>,
+) {
     loop {
-        let mut procs = get_process_list(&pool);
+        let mut procs = get_process_list(&pool, server.as_str());
+        procs.retain(|row| options.filter.matches(row));
+        let raw_infos: Vec<String> = procs.iter().map(|row| row.info.clone()).collect();
         for process in procs.iter_mut() {
             let info = normalize_query(process.info.as_str());
             (*process).info = info;
         }
 
-        summ.update(procs.iter().map(|x| x.info.clone()).collect());
+        if let Some(store) = store {
+            let queries: Vec<(String, String)> = procs
+                .iter()
+                .zip(raw_infos.iter())
+                .map(|(row, raw)| (row.info.clone(), raw.clone()))
+                .collect();
+            let t = now().to_local();
+            let sampled_at = strftime("%Y-%m-%d %H:%M:%S", &t).expect("fail strftime(ymdhms)");
+            let samples = samples_from_queries(sampled_at.as_str(), &queries);
+            store.lock().expect("store mutex poisoned").save_bulk(&samples).expect("fail save samples to store");
+        }
+
+        summ.lock().expect("summarizer mutex poisoned").update(&procs, options.group_by);
+
+        thread::sleep(Duration::from_millis((1000. * options.interval) as u64));
+    }
+}
+
+fn exec_profile<T: Summarize + Send + 'static>(
+    pools: Vec<(String, Pool)>,
+    summ: T,
+    options: MyprofilerOption,
+    store: Option<SharedStore>,
+    mut sink: ReportSink,
+) {
+    let options = Arc::new(options);
+    let summ = Arc::new(Mutex::new(summ));
+
+    for (server, pool) in pools {
+        let summ = Arc::clone(&summ);
+        let options = Arc::clone(&options);
+        let store = store.clone();
+        thread::spawn(move || sample_loop(pool, server, &summ, &options, &store));
+    }
+
+    let mut cnt = 0;
+    loop {
+        thread::sleep(Duration::from_millis((1000. * options.interval) as u64));
 
         cnt += 1;
         if cnt >= options.delay {
             cnt = 0;
             let t = now().to_local();
-            println!(
-                "##  {}.{:03} {}",
+            let timestamp = format!(
+                "{}.{:03} {}",
                 strftime("%Y-%m-%d %H:%M:%S", &t).expect("fail strftime(ymdhms)"),
                 t.tm_nsec / 1000_000,
                 strftime("%z", &t).expect("fail strftime(z)")
             );
-            summ.show(options.top);
+            if options.format == OutputFormat::Text {
+                sink.write_line(format!("##  {}", timestamp).as_str());
+            }
+            let report = summ.lock().expect("summarizer mutex poisoned").show(options.top, options.format, timestamp.as_str());
+            sink.write_line(report.as_str());
+        }
+    }
+}
+
+fn run_report(args: &[String]) -> Result<(), String> {
+    let mut opts = Options::new();
+    opts.optopt("", "store", "path to the myprofiler sqlite store", "PATH");
+    opts.optopt("", "from", "start of the time range (YYYY-MM-DD HH:MM:SS)", "TIME");
+    opts.optopt("", "to", "end of the time range (YYYY-MM-DD HH:MM:SS)", "TIME");
+    opts.optopt("", "top", "print top N query (default: 10)", "N");
+    opts.optopt("", "format", "output format: text, json or ndjson (default: text)", "FORMAT");
+
+    let matches = match opts.parse(args) {
+        Ok(m) => m,
+        Err(e) => {
+            print_usage(opts);
+            eprintln!("{}", e);
+            process::exit(1);
         }
+    };
 
-        thread::sleep(Duration::from_millis((1000. * options.interval) as u64));
+    let from = matches.opt_str("from").unwrap_or_else(|| "0000-00-00 00:00:00".to_string());
+    let to = matches.opt_str("to").unwrap_or_else(|| "9999-99-99 99:99:99".to_string());
+    let top = opts2v!(matches, opts, "top", u32, 10);
+    let format = opts2v!(matches, opts, "format", OutputFormat, OutputFormat::Text);
+
+    let store_path = match matches.opt_str("store") {
+        Some(p) => p,
+        None => {
+            print_usage(opts);
+            process::exit(1);
+        }
+    };
+
+    let store = SqliteStorage::open(store_path.as_str()).expect("fail open store");
+    let rows = store.range(from.as_str(), to.as_str()).expect("fail query store");
+
+    let mut summ: HashMap<String, i64> = HashMap::new();
+    for row in rows {
+        *summ.entry(row.query_fingerprint).or_insert(0) += row.count;
     }
+    println!("{}", render_summary(&summ, top, format, format!("{} .. {}", from, to).as_str()));
+
+    Ok(())
 }
 
 fn main() -> Result<(), String> {
+    let args: Vec<String> = env::args().collect();
+    if args.len() > 1 && args[1] == "report" {
+        return run_report(&args[2..]);
+    }
+
     let mut opts = Options::new();
-    opts.optopt("h", "host", "mysql hostname", "HOSTNAME");
+    opts.optopt("h", "host", "mysql hostname(s), comma-separated (host[:port],...) to profile a cluster", "HOSTNAME");
     opts.optopt("u", "user", "mysql user", "USER");
     opts.optopt("p", "password", "mysql password", "PASSWORD");
     opts.optopt("", "port", "mysql port", "PORT");
@@ -256,17 +579,25 @@ fn main() -> Result<(), String> {
         "(int) Show summary for each `delay` samples. -interval=0.1 -delay=30 shows summary for every 3sec",
         "N",
     );
-    let args: Vec<String> = env::args().collect();
+    opts.optopt("", "format", "output format: text, json or ndjson (default: text)", "FORMAT");
+    opts.optopt("", "store", "path to a sqlite store; persist every sample for later `report`", "PATH");
+    opts.optopt("", "filter-user", "only count queries from this user", "USER");
+    opts.optopt("", "exclude-user", "exclude queries from this user", "USER");
+    opts.optopt("", "filter-host", "only count queries from this host", "HOST");
+    opts.optopt("", "filter-db", "only count queries from this db", "DB");
+    opts.optopt("", "min-time", "only count queries running at least N seconds (default: 0)", "N");
+    opts.optopt("", "group-by", "group summary key by query, user, host, db or server (default: query)", "DIM");
+    opts.optopt("", "out-file", "write summaries to this file instead of stdout; reopened on SIGHUP", "PATH");
     let matches = match opts.parse(&args[1..]) {
         Ok(m) => m,
         Err(e) => {
             print_usage(opts);
-            println!("{}", e);
+            eprintln!("{}", e);
             process::exit(1);
         }
     };
 
-    let host = match matches.opt_str("host") {
+    let host_arg = match matches.opt_str("host") {
         Some(v) => v,
         None => "localhost".to_string(),
     };
@@ -287,24 +618,46 @@ fn main() -> Result<(), String> {
         interval: opts2v!(matches, opts, "interval", f32, 1.0),
         delay: opts2v!(matches, opts, "delay", i32, 1),
         top: opts2v!(matches, opts, "top", u32, 10),
+        format: opts2v!(matches, opts, "format", OutputFormat, OutputFormat::Text),
+        group_by: opts2v!(matches, opts, "group-by", GroupBy, GroupBy::Query),
+        filter: ProcessFilter {
+            filter_user: matches.opt_str("filter-user"),
+            exclude_user: matches.opt_str("exclude-user"),
+            filter_host: matches.opt_str("filter-host"),
+            filter_db: matches.opt_str("filter-db"),
+            min_time: opts2v!(matches, opts, "min-time", i32, 0),
+        },
     };
 
-    let url = format!(
-        "mysql://{user}:{password}@{host}:{port}",
-        user = user,
-        password = password,
-        host = host,
-        port = port
-    );
-    let opts = Opts::from_url(url.as_str()).expect("invalid dsn");
-    let pool = Pool::new_manual(1, 1, opts).expect("fail get mysql connection");
+    let pools: Vec<(String, Pool)> = parse_hosts(host_arg.as_str(), port)
+        .into_iter()
+        .map(|(host, port)| {
+            let url = format!(
+                "mysql://{user}:{password}@{host}:{port}",
+                user = user,
+                password = password,
+                host = host,
+                port = port
+            );
+            let opts = Opts::from_url(url.as_str()).expect("invalid dsn");
+            let pool = Pool::new_manual(1, 4, opts).expect("fail get mysql connection");
+            (host, pool)
+        })
+        .collect();
+
+    let store: Option<SharedStore> = matches.opt_str("store").map(|path| {
+        let store = SqliteStorage::open(path.as_str()).expect("fail open store");
+        Arc::new(Mutex::new(Box::new(store) as Box<dyn Storage + Send>))
+    });
+
+    let sink = ReportSink::new(matches.opt_str("out-file"));
 
     if last == 0 {
         let summ: Summarizer = Summarize::new(last);
-        exec_profile(&pool, summ, &options);
+        exec_profile(pools, summ, options, store, sink);
     } else {
         let summ: RecentSummarizer = Summarize::new(last);
-        exec_profile(&pool, summ, &options);
+        exec_profile(pools, summ, options, store, sink);
     }
 
     Ok(())
@@ -328,4 +681,127 @@ mod tests {
             assert!(normalize_query(pat) == ret);
         }
     }
+
+    #[test]
+    fn test_escape_json() {
+        assert_eq!(escape_json("select 1"), "select 1");
+        assert_eq!(escape_json("a \"b\" \\c\\"), "a \\\"b\\\" \\\\c\\\\");
+        assert_eq!(escape_json("line1\nline2\ttab"), "line1\\nline2\\ttab");
+    }
+
+    #[test]
+    fn test_render_summary_json() {
+        let mut summ = HashMap::new();
+        summ.insert("select N".to_string(), 3);
+        let rendered = render_summary(&summ, 10, OutputFormat::Json, "2026-07-28 00:00:00");
+        assert!(rendered.contains("\"timestamp\":\"2026-07-28 00:00:00\""));
+        assert!(rendered.contains("\"query\":\"select N\""));
+        assert!(rendered.contains("\"count\":3"));
+    }
+
+    #[test]
+    fn test_render_summary_ndjson_is_single_line() {
+        let mut summ = HashMap::new();
+        summ.insert("select N".to_string(), 1);
+        summ.insert("update N".to_string(), 2);
+        let rendered = render_summary(&summ, 10, OutputFormat::Ndjson, "2026-07-28 00:00:00");
+        assert_eq!(rendered.lines().count(), 1);
+        assert!(rendered.starts_with("{\"timestamp\":\"2026-07-28 00:00:00\",\"queries\":["));
+    }
+
+    #[test]
+    fn test_render_summary_top_n() {
+        let mut summ = HashMap::new();
+        summ.insert("a".to_string(), 1);
+        summ.insert("b".to_string(), 2);
+        summ.insert("c".to_string(), 3);
+        let rendered = render_summary(&summ, 1, OutputFormat::Text, "ts");
+        assert_eq!(rendered, "   3 c");
+    }
+
+    #[test]
+    fn test_parse_hosts() {
+        assert_eq!(
+            parse_hosts("host1,host2:3307,host3", 3306),
+            vec![
+                ("host1".to_string(), 3306),
+                ("host2".to_string(), 3307),
+                ("host3".to_string(), 3306),
+            ]
+        );
+        assert_eq!(parse_hosts("host1", 3306), vec![("host1".to_string(), 3306)]);
+    }
+
+    fn sample_row() -> FullProcessList {
+        FullProcessList {
+            id: 1,
+            user: "app".to_string(),
+            host: "10.0.0.1".to_string(),
+            db: "prod".to_string(),
+            command: "Query".to_string(),
+            time: 5,
+            state: "".to_string(),
+            info: "select 1".to_string(),
+            server: "db1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_process_filter_matches() {
+        let row = sample_row();
+
+        let no_filter = ProcessFilter {
+            filter_user: None,
+            exclude_user: None,
+            filter_host: None,
+            filter_db: None,
+            min_time: 0,
+        };
+        assert!(no_filter.matches(&row));
+
+        let wrong_user = ProcessFilter {
+            filter_user: Some("other".to_string()),
+            exclude_user: None,
+            filter_host: None,
+            filter_db: None,
+            min_time: 0,
+        };
+        assert!(!wrong_user.matches(&row));
+
+        let excluded_user = ProcessFilter {
+            filter_user: None,
+            exclude_user: Some("app".to_string()),
+            filter_host: None,
+            filter_db: None,
+            min_time: 0,
+        };
+        assert!(!excluded_user.matches(&row));
+
+        let wrong_host = ProcessFilter {
+            filter_user: None,
+            exclude_user: None,
+            filter_host: Some("10.0.0.2".to_string()),
+            filter_db: None,
+            min_time: 0,
+        };
+        assert!(!wrong_host.matches(&row));
+
+        let wrong_db = ProcessFilter {
+            filter_user: None,
+            exclude_user: None,
+            filter_host: None,
+            filter_db: Some("other".to_string()),
+            min_time: 0,
+        };
+        assert!(!wrong_db.matches(&row));
+
+        let too_fast = ProcessFilter {
+            filter_user: None,
+            exclude_user: None,
+            filter_host: None,
+            filter_db: None,
+            min_time: 10,
+        };
+        assert!(!too_fast.matches(&row));
+    }
 }
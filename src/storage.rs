@@ -0,0 +1,78 @@
+use rusqlite::{params, Connection};
+
+/// One normalized query observed during a sampling interval, ready to persist.
+#[derive(Debug, Clone)]
+pub struct QuerySample {
+    pub sampled_at: String,
+    pub query_fingerprint: String,
+    pub query_text: String,
+    pub count: i64,
+}
+
+pub trait Storage {
+    fn save_bulk(&mut self, samples: &[QuerySample]) -> rusqlite::Result<()>;
+    fn range(&self, from: &str, to: &str) -> rusqlite::Result<Vec<QuerySample>>;
+    fn before(&self, timestamp: &str, count: u32) -> rusqlite::Result<Vec<QuerySample>>;
+}
+
+pub struct SqliteStorage {
+    conn: Connection,
+}
+
+impl SqliteStorage {
+    pub fn open(path: &str) -> rusqlite::Result<SqliteStorage> {
+        let conn = Connection::open(path)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS samples (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                sampled_at TEXT NOT NULL,
+                query_fingerprint TEXT NOT NULL,
+                query_text TEXT NOT NULL,
+                count INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_samples_sampled_at ON samples (sampled_at)", [])?;
+        Ok(SqliteStorage { conn })
+    }
+}
+
+impl Storage for SqliteStorage {
+    fn save_bulk(&mut self, samples: &[QuerySample]) -> rusqlite::Result<()> {
+        let tx = self.conn.transaction()?;
+        for sample in samples {
+            tx.execute(
+                "INSERT INTO samples (sampled_at, query_fingerprint, query_text, count) VALUES (?1, ?2, ?3, ?4)",
+                params![sample.sampled_at, sample.query_fingerprint, sample.query_text, sample.count],
+            )?;
+        }
+        tx.commit()
+    }
+
+    fn range(&self, from: &str, to: &str) -> rusqlite::Result<Vec<QuerySample>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT sampled_at, query_fingerprint, query_text, count FROM samples \
+             WHERE sampled_at >= ?1 AND sampled_at <= ?2",
+        )?;
+        let rows = stmt.query_map(params![from, to], row_to_sample)?;
+        rows.collect()
+    }
+
+    fn before(&self, timestamp: &str, count: u32) -> rusqlite::Result<Vec<QuerySample>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT sampled_at, query_fingerprint, query_text, count FROM samples \
+             WHERE sampled_at <= ?1 ORDER BY sampled_at DESC LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![timestamp, count], row_to_sample)?;
+        rows.collect()
+    }
+}
+
+fn row_to_sample(row: &rusqlite::Row) -> rusqlite::Result<QuerySample> {
+    Ok(QuerySample {
+        sampled_at: row.get(0)?,
+        query_fingerprint: row.get(1)?,
+        query_text: row.get(2)?,
+        count: row.get(3)?,
+    })
+}